@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+
+/// One line of the audit log: an operator action against a worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub session_id: String,
+    pub command: String,
+    pub outcome: String,
+}
+
+fn audit_log_path() -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    Some(home.join(".orchestration").join("ppds-orchestration").join("audit.log"))
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Append an entry recording an operator action against a worker. Failures
+/// to write are logged but never block the action itself. Uses `tokio::fs`
+/// rather than `std::fs` since callers invoke this inline on async tasks
+/// (command dispatch, exit handling) where blocking would stall the shared
+/// runtime rather than just the one caller.
+pub async fn record(session_id: &str, command: &str, outcome: &str) {
+    let Some(path) = audit_log_path() else {
+        eprintln!("Could not determine audit log path");
+        return;
+    };
+
+    if let Some(parent) = path.parent() {
+        let _ = tokio::fs::create_dir_all(parent).await;
+    }
+
+    let entry = AuditEntry {
+        timestamp: now_unix(),
+        session_id: session_id.to_string(),
+        command: command.to_string(),
+        outcome: outcome.to_string(),
+    };
+
+    let Ok(line) = serde_json::to_string(&entry) else {
+        return;
+    };
+
+    match OpenOptions::new().create(true).append(true).open(&path).await {
+        Ok(mut file) => {
+            if let Err(e) = file.write_all(format!("{}\n", line).as_bytes()).await {
+                eprintln!("Failed to append to audit log: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Failed to append to audit log: {}", e),
+    }
+}
+
+/// Read the full audit log, oldest entry first.
+pub async fn read_all() -> Vec<AuditEntry> {
+    let Some(path) = audit_log_path() else {
+        return Vec::new();
+    };
+    let Ok(content) = tokio::fs::read_to_string(&path).await else {
+        return Vec::new();
+    };
+
+    content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect()
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// How long a minted confirmation token stays valid.
+const CONFIRMATION_TTL: Duration = Duration::from_secs(60);
+
+/// A destructive capability gated by `CapabilityPolicy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Forward,
+    Cancel,
+}
+
+impl Action {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "forward" => Ok(Action::Forward),
+            "cancel" => Ok(Action::Cancel),
+            other => Err(format!("Unknown action: {}", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Forward => "forward",
+            Action::Cancel => "cancel",
+        }
+    }
+}
+
+/// Which destructive capabilities are granted outright, without requiring a
+/// per-call confirmation token. Borrows Tauri's ACL direction: destructive
+/// commands are denied by default and must be explicitly allowed, either
+/// wholesale via these flags or one call at a time via `request_confirmation`.
+#[derive(Debug, Clone, Copy)]
+pub struct CapabilityPolicy {
+    pub allow_forward: bool,
+    pub allow_cancel: bool,
+}
+
+impl CapabilityPolicy {
+    /// Read from `ORCH_ALLOW_FORWARD` / `ORCH_ALLOW_CANCEL` environment
+    /// variables; both default to denied.
+    pub fn from_env() -> Self {
+        Self {
+            allow_forward: env_flag("ORCH_ALLOW_FORWARD"),
+            allow_cancel: env_flag("ORCH_ALLOW_CANCEL"),
+        }
+    }
+
+    fn allows(&self, action: Action) -> bool {
+        match action {
+            Action::Forward => self.allow_forward,
+            Action::Cancel => self.allow_cancel,
+        }
+    }
+}
+
+fn env_flag(name: &str) -> bool {
+    std::env::var(name)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+struct Grant {
+    action: Action,
+    session_id: String,
+    minted_at: Instant,
+}
+
+/// Single-use confirmation tokens minted by `request_confirmation`, each
+/// scoped to one action against one session and expiring after `ttl`.
+pub struct ConfirmationRegistry {
+    grants: Mutex<HashMap<String, Grant>>,
+    ttl: Duration,
+}
+
+impl ConfirmationRegistry {
+    pub fn new() -> Self {
+        Self::with_ttl(CONFIRMATION_TTL)
+    }
+
+    /// Like `new`, but with an explicit TTL instead of `CONFIRMATION_TTL` —
+    /// used by tests that need to observe expiry without a 60-second sleep.
+    fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            grants: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    pub fn request(&self, action: Action, session_id: &str) -> String {
+        let token = Uuid::new_v4().to_string();
+        self.grants.lock().unwrap().insert(
+            token.clone(),
+            Grant {
+                action,
+                session_id: session_id.to_string(),
+                minted_at: Instant::now(),
+            },
+        );
+        token
+    }
+
+    /// Consume a token if it's valid for `action` against `session_id` and
+    /// hasn't expired. Single-use: valid or not, it's removed on lookup.
+    fn consume(&self, token: &str, action: Action, session_id: &str) -> bool {
+        let mut grants = self.grants.lock().unwrap();
+        match grants.remove(token) {
+            Some(grant) => grant.action == action && grant.session_id == session_id && grant.minted_at.elapsed() < self.ttl,
+            None => false,
+        }
+    }
+}
+
+/// Check whether `action` against `session_id` is authorized, either because
+/// the policy grants the capability outright or because a matching,
+/// unexpired confirmation token was supplied.
+pub fn authorize(
+    policy: &CapabilityPolicy,
+    confirmations: &ConfirmationRegistry,
+    action: Action,
+    session_id: &str,
+    confirmation_token: Option<&str>,
+) -> Result<(), String> {
+    if policy.allows(action) {
+        return Ok(());
+    }
+
+    if let Some(token) = confirmation_token {
+        if confirmations.consume(token, action, session_id) {
+            return Ok(());
+        }
+    }
+
+    Err(format!(
+        "'{}' on session {} requires the matching capability or a confirmation token from request_confirmation",
+        action.as_str(),
+        session_id
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allow_forward: bool, allow_cancel: bool) -> CapabilityPolicy {
+        CapabilityPolicy { allow_forward, allow_cancel }
+    }
+
+    #[test]
+    fn authorize_succeeds_when_policy_allows_outright() {
+        let policy = policy(true, false);
+        let confirmations = ConfirmationRegistry::new();
+        assert!(authorize(&policy, &confirmations, Action::Forward, "session-1", None).is_ok());
+    }
+
+    #[test]
+    fn authorize_fails_without_policy_or_token() {
+        let policy = policy(false, false);
+        let confirmations = ConfirmationRegistry::new();
+        assert!(authorize(&policy, &confirmations, Action::Forward, "session-1", None).is_err());
+    }
+
+    #[test]
+    fn authorize_succeeds_with_a_valid_matching_token() {
+        let policy = policy(false, false);
+        let confirmations = ConfirmationRegistry::new();
+        let token = confirmations.request(Action::Forward, "session-1");
+        assert!(authorize(&policy, &confirmations, Action::Forward, "session-1", Some(&token)).is_ok());
+    }
+
+    #[test]
+    fn token_is_single_use() {
+        let policy = policy(false, false);
+        let confirmations = ConfirmationRegistry::new();
+        let token = confirmations.request(Action::Forward, "session-1");
+        assert!(authorize(&policy, &confirmations, Action::Forward, "session-1", Some(&token)).is_ok());
+        assert!(authorize(&policy, &confirmations, Action::Forward, "session-1", Some(&token)).is_err());
+    }
+
+    #[test]
+    fn token_is_rejected_for_the_wrong_action() {
+        let policy = policy(false, false);
+        let confirmations = ConfirmationRegistry::new();
+        let token = confirmations.request(Action::Forward, "session-1");
+        assert!(authorize(&policy, &confirmations, Action::Cancel, "session-1", Some(&token)).is_err());
+    }
+
+    #[test]
+    fn token_is_rejected_for_the_wrong_session() {
+        let policy = policy(false, false);
+        let confirmations = ConfirmationRegistry::new();
+        let token = confirmations.request(Action::Forward, "session-1");
+        assert!(authorize(&policy, &confirmations, Action::Forward, "session-2", Some(&token)).is_err());
+    }
+
+    #[test]
+    fn token_expires_after_its_ttl_elapses() {
+        let policy = policy(false, false);
+        let confirmations = ConfirmationRegistry::with_ttl(Duration::from_millis(10));
+        let token = confirmations.request(Action::Forward, "session-1");
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(authorize(&policy, &confirmations, Action::Forward, "session-1", Some(&token)).is_err());
+    }
+}
@@ -1,193 +1,217 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
-use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
-use std::sync::mpsc::channel;
-use std::time::Duration;
-use tauri::{AppHandle, Emitter, Manager};
-
-/// Session state matching the TypeScript schema
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct SessionState {
-    pub id: String,
-    pub issue_number: i32,
-    pub issue_title: String,
-    pub status: String,
-    pub branch: String,
-    pub worktree_path: String,
-    pub started_at: String,
-    pub last_heartbeat: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub stuck_reason: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub forwarded_message: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub pull_request_url: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub worktree_status: Option<WorktreeStatus>,
+mod audit;
+mod capability;
+mod process;
+mod session;
+mod source;
+
+use audit::AuditEntry;
+use capability::{Action, CapabilityPolicy, ConfirmationRegistry};
+use process::CommandRegistry;
+use session::{session_event_name, SessionEvent, SessionState};
+use source::{LocalSessionSource, RemoteConnectOptions, RemoteOptions, RemoteSessionSource, SessionSource};
+use std::sync::{Arc, Mutex};
+use tauri::{async_runtime::JoinHandle, AppHandle, Emitter, Manager};
+use tokio::sync::mpsc::unbounded_channel;
+
+/// The currently active session source, its watch, and the registry of
+/// commands it has dispatched. The source is swapped out wholesale by
+/// `connect_remote` so the rest of the app never needs to know whether it's
+/// talking to the local filesystem or a remote host.
+struct AppState {
+    source: Mutex<Arc<dyn SessionSource>>,
+    watch: Mutex<Option<ActiveWatch>>,
+    command_registry: Arc<CommandRegistry>,
+    capability_policy: CapabilityPolicy,
+    confirmations: ConfirmationRegistry,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct WorktreeStatus {
-    pub files_changed: i32,
-    pub insertions: i32,
-    pub deletions: i32,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub last_commit_message: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tests_passing: Option<bool>,
+/// The two tasks backing a live watch: the one the `SessionSource` itself
+/// spawned to do the actual watching (a local `notify::Watcher`, or a remote
+/// `ssh` child and its stdout reader), and the one relaying its events to
+/// the frontend. Both must be aborted together when the source changes —
+/// aborting only the bridge would leave the source's task running forever.
+struct ActiveWatch {
+    source_watch: JoinHandle<()>,
+    bridge: JoinHandle<()>,
 }
 
-/// Event sent to frontend when sessions change
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct SessionEvent {
-    pub event_type: String, // "add", "update", "remove"
-    pub session: Option<SessionState>,
-    pub session_id: Option<String>,
-}
-
-/// Get the sessions directory path
-fn get_sessions_dir() -> Option<PathBuf> {
-    let home = dirs::home_dir()?;
-    Some(home.join(".orchestration").join("ppds-orchestration").join("sessions"))
+impl ActiveWatch {
+    fn abort(&self) {
+        self.source_watch.abort();
+        self.bridge.abort();
+    }
 }
 
-/// Load all sessions from the sessions directory
-fn load_all_sessions(sessions_dir: &PathBuf) -> Vec<SessionState> {
-    let mut sessions = Vec::new();
-
-    if let Ok(entries) = fs::read_dir(sessions_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().map_or(false, |ext| ext == "json") {
-                if let Ok(content) = fs::read_to_string(&path) {
-                    if let Ok(session) = serde_json::from_str::<SessionState>(&content) {
-                        sessions.push(session);
-                    }
-                }
-            }
+/// Establish a watch against `source` and forward every event it produces to
+/// the frontend, both on the global `session-event` channel and on a
+/// per-session scoped channel.
+async fn establish_watch(app_handle: AppHandle, source: Arc<dyn SessionSource>) -> ActiveWatch {
+    let (tx, mut rx) = unbounded_channel::<SessionEvent>();
+    let source_watch = source.watch(tx).await;
+
+    let bridge = tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            let session_id = match &event {
+                SessionEvent::Added(session) | SessionEvent::Updated(session) => session.id.clone(),
+                SessionEvent::Removed { session_id } => session_id.clone(),
+            };
+            let scoped_event = session_event_name(&session_id);
+            let _ = app_handle.emit("session-event", &event);
+            let _ = app_handle.emit(&scoped_event, &event);
         }
-    }
+    });
 
-    sessions
+    ActiveWatch { source_watch, bridge }
 }
 
 /// Tauri command: Get all sessions
 #[tauri::command]
-fn get_sessions() -> Vec<SessionState> {
-    get_sessions_dir()
-        .map(|dir| load_all_sessions(&dir))
-        .unwrap_or_default()
+async fn get_sessions(state: tauri::State<'_, AppState>) -> Result<Vec<SessionState>, String> {
+    let source = state.source.lock().unwrap().clone();
+    Ok(source.snapshot().await)
 }
 
-/// Tauri command: Forward a message to a worker
+/// Tauri command: Forward a message to a worker. Requires the `allow_forward`
+/// capability or a confirmation token from `request_confirmation`. Returns
+/// the id of the dispatched command; progress streams in as
+/// `orch-output`/`orch-exit` events rather than being awaited here.
 #[tauri::command]
-async fn forward_message(session_id: String, message: String) -> Result<(), String> {
-    let output = std::process::Command::new("orch")
-        .args(["forward", &session_id, &message])
-        .output()
-        .map_err(|e| format!("Failed to run orch forward: {}", e))?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
+async fn forward_message(
+    state: tauri::State<'_, AppState>,
+    session_id: String,
+    message: String,
+    confirmation_token: Option<String>,
+) -> Result<String, String> {
+    let authorization = capability::authorize(
+        &state.capability_policy,
+        &state.confirmations,
+        Action::Forward,
+        &session_id,
+        confirmation_token.as_deref(),
+    );
+    if let Err(e) = authorization {
+        audit::record(&session_id, "forward", &format!("denied: {}", e)).await;
+        return Err(e);
+    }
+
+    let source = state.source.lock().unwrap().clone();
+    let result = source.forward_message(&session_id, &message).await;
+    match &result {
+        Ok(command_id) => audit::record(&session_id, "forward", &format!("dispatched: {}", command_id)).await,
+        Err(e) => audit::record(&session_id, "forward", &format!("failed: {}", e)).await,
     }
+    result
 }
 
-/// Tauri command: Cancel a session
+/// Tauri command: Cancel a session. Requires the `allow_cancel` capability or
+/// a confirmation token from `request_confirmation`. Returns the id of the
+/// dispatched command; progress streams in as `orch-output`/`orch-exit`
+/// events rather than being awaited here.
 #[tauri::command]
-async fn cancel_session(session_id: String) -> Result<(), String> {
-    let output = std::process::Command::new("orch")
-        .args(["cancel", &session_id])
-        .output()
-        .map_err(|e| format!("Failed to run orch cancel: {}", e))?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
+async fn cancel_session(
+    state: tauri::State<'_, AppState>,
+    session_id: String,
+    confirmation_token: Option<String>,
+) -> Result<String, String> {
+    let authorization = capability::authorize(
+        &state.capability_policy,
+        &state.confirmations,
+        Action::Cancel,
+        &session_id,
+        confirmation_token.as_deref(),
+    );
+    if let Err(e) = authorization {
+        audit::record(&session_id, "cancel", &format!("denied: {}", e)).await;
+        return Err(e);
     }
+
+    let source = state.source.lock().unwrap().clone();
+    let result = source.cancel_session(&session_id).await;
+    match &result {
+        Ok(command_id) => audit::record(&session_id, "cancel", &format!("dispatched: {}", command_id)).await,
+        Err(e) => audit::record(&session_id, "cancel", &format!("failed: {}", e)).await,
+    }
+    result
 }
 
-/// Start watching the sessions directory for changes
-fn start_session_watcher(app_handle: AppHandle) {
-    std::thread::spawn(move || {
-        let sessions_dir = match get_sessions_dir() {
-            Some(dir) => dir,
-            None => {
-                eprintln!("Could not determine sessions directory");
-                return;
-            }
-        };
-
-        // Create directory if it doesn't exist
-        let _ = fs::create_dir_all(&sessions_dir);
-
-        let (tx, rx) = channel();
-
-        let config = Config::default().with_poll_interval(Duration::from_secs(1));
-        let mut watcher: RecommendedWatcher = match Watcher::new(tx, config) {
-            Ok(w) => w,
-            Err(e) => {
-                eprintln!("Failed to create watcher: {}", e);
-                return;
-            }
-        };
-
-        if let Err(e) = watcher.watch(&sessions_dir, RecursiveMode::NonRecursive) {
-            eprintln!("Failed to watch directory: {}", e);
-            return;
-        }
+/// Tauri command: Kill an in-flight `orch`/`ssh` command started by
+/// `forward_message` or `cancel_session`. At least as destructive as the
+/// action it's interrupting, so it's gated by the same capability/
+/// confirmation-token check that action required, and audited the same way.
+#[tauri::command]
+async fn cancel_command(
+    state: tauri::State<'_, AppState>,
+    id: String,
+    confirmation_token: Option<String>,
+) -> Result<(), String> {
+    let Some((session_id, action)) = state.command_registry.describe(&id).await else {
+        return Err(format!("No in-flight command with id {}", id));
+    };
+
+    let authorization = capability::authorize(
+        &state.capability_policy,
+        &state.confirmations,
+        action,
+        &session_id,
+        confirmation_token.as_deref(),
+    );
+    if let Err(e) = authorization {
+        audit::record(&session_id, "cancel_command", &format!("denied: {}", e)).await;
+        return Err(e);
+    }
 
-        println!("Watching sessions directory: {:?}", sessions_dir);
-
-        for result in rx {
-            match result {
-                Ok(event) => {
-                    // Process file changes
-                    for path in event.paths {
-                        if path.extension().map_or(false, |ext| ext == "json") {
-                            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-                            // Determine event type based on file existence
-                            if path.exists() {
-                                // File was created or modified
-                                if let Ok(content) = fs::read_to_string(&path) {
-                                    if let Ok(session) = serde_json::from_str::<SessionState>(&content) {
-                                        let event = SessionEvent {
-                                            event_type: "update".to_string(),
-                                            session: Some(session),
-                                            session_id: None,
-                                        };
-                                        let _ = app_handle.emit("session-event", event);
-                                    }
-                                }
-                            } else {
-                                // File was deleted
-                                let session_id = file_name.trim_end_matches(".json").to_string();
-                                let event = SessionEvent {
-                                    event_type: "remove".to_string(),
-                                    session: None,
-                                    session_id: Some(session_id),
-                                };
-                                let _ = app_handle.emit("session-event", event);
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Watch error: {}", e);
-                }
-            }
-        }
-    });
+    let result = state.command_registry.cancel(&id).await;
+    match &result {
+        Ok(()) => audit::record(&session_id, "cancel_command", &format!("killed command {}", id)).await,
+        Err(e) => audit::record(&session_id, "cancel_command", &format!("failed: {}", e)).await,
+    }
+    result
+}
+
+/// Tauri command: Mint a single-use confirmation token authorizing `action`
+/// ("forward" or "cancel") against `session_id`, for callers that don't hold
+/// the matching capability outright.
+#[tauri::command]
+async fn request_confirmation(state: tauri::State<'_, AppState>, action: String, session_id: String) -> Result<String, String> {
+    let action = Action::parse(&action)?;
+    Ok(state.confirmations.request(action, &session_id))
+}
+
+/// Tauri command: Read the audit log of forward/cancel actions taken against
+/// workers.
+#[tauri::command]
+async fn get_audit_log() -> Vec<AuditEntry> {
+    audit::read_all().await
+}
+
+/// Tauri command: Switch the active session source to a host reachable over
+/// SSH, tearing down the previous watch (both its relay task and the
+/// source's own watcher task) and starting a new one.
+#[tauri::command]
+async fn connect_remote(
+    app_handle: AppHandle,
+    state: tauri::State<'_, AppState>,
+    host: String,
+    opts: RemoteConnectOptions,
+) -> Result<(), String> {
+    let new_source: Arc<dyn SessionSource> = Arc::new(RemoteSessionSource::new(
+        RemoteOptions::new(host, opts),
+        app_handle.clone(),
+        state.command_registry.clone(),
+    ));
+
+    if let Some(watch) = state.watch.lock().unwrap().take() {
+        watch.abort();
+    }
+
+    *state.source.lock().unwrap() = new_source.clone();
+    let watch = establish_watch(app_handle, new_source).await;
+    *state.watch.lock().unwrap() = Some(watch);
+
+    Ok(())
 }
 
 fn main() {
@@ -196,11 +220,25 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             get_sessions,
             forward_message,
-            cancel_session
+            cancel_session,
+            cancel_command,
+            connect_remote,
+            request_confirmation,
+            get_audit_log
         ])
         .setup(|app| {
-            // Start watching sessions directory
-            start_session_watcher(app.handle().clone());
+            let command_registry = Arc::new(CommandRegistry::new());
+            let source: Arc<dyn SessionSource> = Arc::new(LocalSessionSource::new(app.handle().clone(), command_registry.clone()));
+            let watch = tauri::async_runtime::block_on(establish_watch(app.handle().clone(), source.clone()));
+
+            app.manage(AppState {
+                source: Mutex::new(source),
+                watch: Mutex::new(Some(watch)),
+                command_registry,
+                capability_policy: CapabilityPolicy::from_env(),
+                confirmations: ConfirmationRegistry::new(),
+            });
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,217 @@
+use crate::audit;
+use crate::capability::Action;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use uuid::Uuid;
+
+/// How often we poll an in-flight child for exit, while separate tasks
+/// stream its stdout/stderr. Cheap enough that `cancel_command` never waits long to
+/// acquire the registry lock.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long `describe`/`cancel` will retry looking up a just-dispatched
+/// command before giving up, so a lookup issued immediately after `spawn`
+/// returns can't race the registry insert that happens inside the spawned
+/// task.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(2);
+const LOOKUP_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Emitted once per line as a spawned command produces output.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrchOutputEvent {
+    pub id: String,
+    pub stream: OutputStream,
+    pub line: String,
+}
+
+/// Emitted once per spawned command: when its child process exits or is
+/// killed, or, with no `code`, when the child fails to start.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrchExitEvent {
+    pub id: String,
+    pub code: Option<i32>,
+}
+
+/// The action and session a dispatched command belongs to, alongside the
+/// child running it. Kept together so `cancel_command` can be gated by the
+/// same capability check as the `forward_message`/`cancel_session` call that
+/// dispatched it.
+struct Tracked {
+    child: Child,
+    session_id: String,
+    action: Action,
+}
+
+/// Registry of in-flight child processes, keyed by an id handed back to the
+/// frontend the moment a command is spawned, so a long-running `orch`
+/// invocation can stream its progress and be cancelled instead of blocking
+/// the caller until it exits.
+#[derive(Default)]
+pub struct CommandRegistry {
+    children: Mutex<HashMap<String, Tracked>>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn `program args...` on behalf of `action` against `session_id`,
+    /// streaming its stdout/stderr to the frontend as `orch-output` events
+    /// and emitting a terminal `orch-exit` event once it completes. Also
+    /// appends the final outcome to the audit log. Returns the command's id
+    /// immediately.
+    pub fn spawn(
+        registry: Arc<CommandRegistry>,
+        app_handle: AppHandle,
+        session_id: String,
+        action: Action,
+        program: &str,
+        args: Vec<String>,
+    ) -> String {
+        let id = Uuid::new_v4().to_string();
+        let task_id = id.clone();
+        let program = program.to_string();
+
+        tauri::async_runtime::spawn(async move {
+            let mut child = match Command::new(&program)
+                .args(&args)
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::piped())
+                .spawn()
+            {
+                Ok(child) => child,
+                Err(e) => {
+                    eprintln!("Failed to spawn {} {:?}: {}", program, args, e);
+                    audit::record(&session_id, action.as_str(), &format!("failed to start: {}", e)).await;
+                    let _ = app_handle.emit("orch-exit", OrchExitEvent { id: task_id, code: None });
+                    return;
+                }
+            };
+
+            if let Some(stdout) = child.stdout.take() {
+                spawn_line_reader(app_handle.clone(), task_id.clone(), OutputStream::Stdout, stdout);
+            }
+            if let Some(stderr) = child.stderr.take() {
+                spawn_line_reader(app_handle.clone(), task_id.clone(), OutputStream::Stderr, stderr);
+            }
+
+            registry.children.lock().unwrap().insert(
+                task_id.clone(),
+                Tracked {
+                    child,
+                    session_id: session_id.clone(),
+                    action,
+                },
+            );
+
+            let code = loop {
+                let status = {
+                    let mut children = registry.children.lock().unwrap();
+                    match children.get_mut(&task_id) {
+                        Some(tracked) => tracked.child.try_wait(),
+                        // Removed out from under us: cancel_command killed it.
+                        None => break None,
+                    }
+                };
+
+                match status {
+                    Ok(Some(status)) => break status.code(),
+                    Ok(None) => tokio::time::sleep(WAIT_POLL_INTERVAL).await,
+                    Err(e) => {
+                        eprintln!("Failed to poll {} {:?}: {}", program, args, e);
+                        break None;
+                    }
+                }
+            };
+
+            // If `cancel` already removed this entry, the command was killed
+            // rather than having exited on its own; still remove defensively
+            // in case polling raced a kill and broke out with an exit code.
+            let was_cancelled = registry.children.lock().unwrap().remove(&task_id).is_none();
+
+            let outcome = if was_cancelled {
+                "cancelled".to_string()
+            } else {
+                match code {
+                    Some(code) => format!("exited with code {}", code),
+                    None => "exited with no status".to_string(),
+                }
+            };
+            audit::record(&session_id, action.as_str(), &outcome).await;
+            let _ = app_handle.emit("orch-exit", OrchExitEvent { id: task_id, code });
+        });
+
+        id
+    }
+
+    /// Look up the session id and action a dispatched command belongs to,
+    /// without killing it, so `cancel_command` can be gated by the same
+    /// capability check as the original `forward_message`/`cancel_session`
+    /// call. Retries for `LOOKUP_TIMEOUT` so a lookup issued immediately
+    /// after `spawn` returns doesn't race the registry insert.
+    pub async fn describe(&self, id: &str) -> Option<(String, Action)> {
+        let deadline = Instant::now() + LOOKUP_TIMEOUT;
+        loop {
+            if let Some(tracked) = self.children.lock().unwrap().get(id) {
+                return Some((tracked.session_id.clone(), tracked.action));
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            tokio::time::sleep(LOOKUP_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Kill an in-flight command by id. Retries for `LOOKUP_TIMEOUT` so a
+    /// cancel requested right after dispatch can't be silently dropped just
+    /// because the registry insert hasn't landed yet.
+    pub async fn cancel(&self, id: &str) -> Result<(), String> {
+        let deadline = Instant::now() + LOOKUP_TIMEOUT;
+        loop {
+            {
+                let mut children = self.children.lock().unwrap();
+                if let Some(tracked) = children.remove(id) {
+                    let mut child = tracked.child;
+                    return child.start_kill().map_err(|e| format!("Failed to kill command {}: {}", id, e));
+                }
+            }
+            if Instant::now() >= deadline {
+                return Err(format!("No in-flight command with id {}", id));
+            }
+            tokio::time::sleep(LOOKUP_POLL_INTERVAL).await;
+        }
+    }
+}
+
+fn spawn_line_reader<R>(app_handle: AppHandle, id: String, stream: OutputStream, reader: R)
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    tauri::async_runtime::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = app_handle.emit(
+                "orch-output",
+                OrchOutputEvent {
+                    id: id.clone(),
+                    stream: stream.clone(),
+                    line,
+                },
+            );
+        }
+    });
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Session state matching the TypeScript schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionState {
+    pub id: String,
+    pub issue_number: i32,
+    pub issue_title: String,
+    pub status: String,
+    pub branch: String,
+    pub worktree_path: String,
+    pub started_at: String,
+    pub last_heartbeat: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stuck_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarded_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull_request_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_status: Option<WorktreeStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorktreeStatus {
+    pub files_changed: i32,
+    pub insertions: i32,
+    pub deletions: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_commit_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tests_passing: Option<bool>,
+}
+
+/// Event sent to frontend when sessions change. Tagged so the frontend can
+/// match on `type` without inferring it from which optional fields are set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SessionEvent {
+    Added(SessionState),
+    Updated(SessionState),
+    Removed { session_id: String },
+}
+
+/// Per-session scoped event name, so a detail view can subscribe to just one
+/// session without filtering every global `session-event` broadcast.
+pub fn session_event_name(session_id: &str) -> String {
+    format!("session://{}", session_id)
+}
@@ -0,0 +1,306 @@
+use super::SessionSource;
+use crate::capability::Action;
+use crate::process::CommandRegistry;
+use crate::session::{SessionEvent, SessionState};
+use async_trait::async_trait;
+use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{async_runtime::JoinHandle, AppHandle};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::time::interval;
+
+/// How long a path must go quiet before we read+parse it.
+const DEFAULT_DEBOUNCE_INTERVAL: Duration = Duration::from_millis(200);
+/// How often we sweep the pending map for paths that have settled.
+const DEBOUNCE_SWEEP_INTERVAL: Duration = Duration::from_millis(50);
+/// Parse attempts before we give up on a changed file (handles atomic-rename
+/// and truncate-then-write races against the `orch` CLI).
+const MAX_PARSE_ATTEMPTS: u32 = 3;
+const PARSE_RETRY_BACKOFF: Duration = Duration::from_millis(50);
+
+/// Get the sessions directory path
+fn get_sessions_dir() -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    Some(home.join(".orchestration").join("ppds-orchestration").join("sessions"))
+}
+
+/// Load all sessions from the sessions directory. Uses `tokio::fs` rather
+/// than `std::fs` since this runs inline on the async watcher task and
+/// blocking here would stall the shared runtime, not just this session.
+async fn load_all_sessions(sessions_dir: &PathBuf) -> Vec<SessionState> {
+    let mut sessions = Vec::new();
+
+    let Ok(mut entries) = tokio::fs::read_dir(sessions_dir).await else {
+        return sessions;
+    };
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().map_or(false, |ext| ext == "json") {
+            if let Ok(content) = tokio::fs::read_to_string(&path).await {
+                if let Ok(session) = serde_json::from_str::<SessionState>(&content) {
+                    sessions.push(session);
+                }
+            }
+        }
+    }
+
+    sessions
+}
+
+/// Read and parse a session file, retrying a few times with a short backoff
+/// so atomic-rename and truncate-then-write writes from the `orch` CLI have
+/// a chance to settle before we give up. Uses `tokio::fs` for the same
+/// reason as `load_all_sessions`.
+async fn read_session_with_retry(path: &PathBuf) -> Option<SessionState> {
+    for attempt in 0..MAX_PARSE_ATTEMPTS {
+        if let Ok(content) = tokio::fs::read_to_string(path).await {
+            if let Ok(session) = serde_json::from_str::<SessionState>(&content) {
+                return Some(session);
+            }
+        }
+        if attempt + 1 < MAX_PARSE_ATTEMPTS {
+            tokio::time::sleep(PARSE_RETRY_BACKOFF).await;
+        }
+    }
+    None
+}
+
+/// Classify `session` as added or updated against the in-memory index,
+/// based solely on whether its id is already known.
+fn classify_change(index: &HashMap<String, SessionState>, session: SessionState) -> SessionEvent {
+    if index.contains_key(&session.id) {
+        SessionEvent::Updated(session)
+    } else {
+        SessionEvent::Added(session)
+    }
+}
+
+/// Recover the session id a removed path belonged to, from its file name.
+fn session_id_from_path(path: &PathBuf) -> String {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    file_name.trim_end_matches(".json").to_string()
+}
+
+/// Emit the appropriate session event for a path that has gone quiet,
+/// classifying it as added/updated/removed against the in-memory index.
+async fn emit_settled_path(path: &PathBuf, index: &mut HashMap<String, SessionState>, sink: &UnboundedSender<SessionEvent>) {
+    if tokio::fs::metadata(path).await.is_ok() {
+        match read_session_with_retry(path).await {
+            Some(session) => {
+                let event = classify_change(index, session.clone());
+                index.insert(session.id.clone(), session);
+                let _ = sink.send(event);
+            }
+            None => {
+                eprintln!("Giving up on {:?} after {} failed parse attempts", path, MAX_PARSE_ATTEMPTS);
+            }
+        }
+    } else {
+        let session_id = session_id_from_path(path);
+        index.remove(&session_id);
+        let _ = sink.send(SessionEvent::Removed { session_id });
+    }
+}
+
+/// Sessions backed by `orch`'s local sessions directory, the original (and
+/// default) source.
+pub struct LocalSessionSource {
+    debounce_interval: Duration,
+    app_handle: AppHandle,
+    registry: Arc<CommandRegistry>,
+}
+
+impl LocalSessionSource {
+    pub fn new(app_handle: AppHandle, registry: Arc<CommandRegistry>) -> Self {
+        Self {
+            debounce_interval: DEFAULT_DEBOUNCE_INTERVAL,
+            app_handle,
+            registry,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionSource for LocalSessionSource {
+    async fn snapshot(&self) -> Vec<SessionState> {
+        match get_sessions_dir() {
+            Some(dir) => load_all_sessions(&dir).await,
+            None => Vec::new(),
+        }
+    }
+
+    async fn watch(&self, sink: UnboundedSender<SessionEvent>) -> JoinHandle<()> {
+        let debounce_interval = self.debounce_interval;
+
+        tauri::async_runtime::spawn(async move {
+            let sessions_dir = match get_sessions_dir() {
+                Some(dir) => dir,
+                None => {
+                    eprintln!("Could not determine sessions directory");
+                    return;
+                }
+            };
+
+            // Create directory if it doesn't exist
+            let _ = tokio::fs::create_dir_all(&sessions_dir).await;
+
+            let (tx, mut rx) = unbounded_channel::<notify::Event>();
+
+            let config = Config::default().with_poll_interval(Duration::from_secs(1));
+            let mut watcher: RecommendedWatcher = match Watcher::new(
+                move |result: notify::Result<notify::Event>| {
+                    if let Ok(event) = result {
+                        let _ = tx.send(event);
+                    }
+                },
+                config,
+            ) {
+                Ok(w) => w,
+                Err(e) => {
+                    eprintln!("Failed to create watcher: {}", e);
+                    return;
+                }
+            };
+
+            if let Err(e) = watcher.watch(&sessions_dir, RecursiveMode::NonRecursive) {
+                eprintln!("Failed to watch directory: {}", e);
+                return;
+            }
+
+            println!("Watching sessions directory: {:?}", sessions_dir);
+
+            // Seed the index from disk so the first change we observe is
+            // correctly classified as an update rather than an add.
+            let mut index: HashMap<String, SessionState> = load_all_sessions(&sessions_dir)
+                .await
+                .into_iter()
+                .map(|session| (session.id.clone(), session))
+                .collect();
+
+            // Paths that have changed recently, along with the time of their
+            // most recent event. A path is only processed once it has gone
+            // `debounce_interval` without a further event.
+            let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+            let mut sweep = interval(DEBOUNCE_SWEEP_INTERVAL);
+
+            loop {
+                tokio::select! {
+                    event = rx.recv() => {
+                        let Some(event) = event else { break };
+                        for path in event.paths {
+                            if path.extension().map_or(false, |ext| ext == "json") {
+                                pending.insert(path, Instant::now());
+                            }
+                        }
+                    }
+                    _ = sweep.tick() => {
+                        let settled: Vec<PathBuf> = pending
+                            .iter()
+                            .filter(|(_, last_seen)| last_seen.elapsed() >= debounce_interval)
+                            .map(|(path, _)| path.clone())
+                            .collect();
+
+                        for path in settled {
+                            pending.remove(&path);
+                            emit_settled_path(&path, &mut index, &sink).await;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    async fn forward_message(&self, session_id: &str, message: &str) -> Result<String, String> {
+        let args = vec!["forward".to_string(), session_id.to_string(), message.to_string()];
+        Ok(CommandRegistry::spawn(
+            self.registry.clone(),
+            self.app_handle.clone(),
+            session_id.to_string(),
+            Action::Forward,
+            "orch",
+            args,
+        ))
+    }
+
+    async fn cancel_session(&self, session_id: &str) -> Result<String, String> {
+        let args = vec!["cancel".to_string(), session_id.to_string()];
+        Ok(CommandRegistry::spawn(
+            self.registry.clone(),
+            self.app_handle.clone(),
+            session_id.to_string(),
+            Action::Cancel,
+            "orch",
+            args,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str) -> SessionState {
+        SessionState {
+            id: id.to_string(),
+            issue_number: 1,
+            issue_title: "title".to_string(),
+            status: "running".to_string(),
+            branch: "branch".to_string(),
+            worktree_path: "/tmp/worktree".to_string(),
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            last_heartbeat: "2026-01-01T00:00:00Z".to_string(),
+            stuck_reason: None,
+            forwarded_message: None,
+            pull_request_url: None,
+            worktree_status: None,
+        }
+    }
+
+    #[test]
+    fn classify_change_is_added_when_id_is_unknown() {
+        let index = HashMap::new();
+        match classify_change(&index, session("a")) {
+            SessionEvent::Added(s) => assert_eq!(s.id, "a"),
+            other => panic!("expected Added, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn classify_change_is_updated_when_id_is_already_indexed() {
+        let mut index = HashMap::new();
+        index.insert("a".to_string(), session("a"));
+        match classify_change(&index, session("a")) {
+            SessionEvent::Updated(s) => assert_eq!(s.id, "a"),
+            other => panic!("expected Updated, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn classify_change_keys_off_id_not_other_fields() {
+        let mut index = HashMap::new();
+        let mut original = session("a");
+        original.status = "stuck".to_string();
+        index.insert("a".to_string(), original);
+
+        let mut changed = session("a");
+        changed.status = "running".to_string();
+        match classify_change(&index, changed) {
+            SessionEvent::Updated(s) => assert_eq!(s.status, "running"),
+            other => panic!("expected Updated, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn session_id_from_path_strips_json_extension() {
+        assert_eq!(session_id_from_path(&PathBuf::from("/sessions/abc-123.json")), "abc-123");
+    }
+
+    #[test]
+    fn session_id_from_path_handles_missing_file_name() {
+        assert_eq!(session_id_from_path(&PathBuf::from("/")), "");
+    }
+}
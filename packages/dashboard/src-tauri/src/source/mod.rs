@@ -0,0 +1,36 @@
+mod local;
+mod remote;
+
+use crate::session::{SessionEvent, SessionState};
+use async_trait::async_trait;
+use tauri::async_runtime::JoinHandle;
+use tokio::sync::mpsc::UnboundedSender;
+
+pub use local::LocalSessionSource;
+pub use remote::{RemoteConnectOptions, RemoteOptions, RemoteSessionSource};
+
+/// A place `orch` sessions can live: the local filesystem, or a remote host
+/// reachable over SSH. `get_sessions` / `forward_message` / `cancel_session`
+/// are all implemented against this trait so the rest of the app doesn't
+/// care where the sessions it's looking at actually run.
+#[async_trait]
+pub trait SessionSource: Send + Sync {
+    /// Current state of every known session.
+    async fn snapshot(&self) -> Vec<SessionState>;
+
+    /// Start watching for session changes, pushing each one into `sink` as
+    /// it's observed. Returns the `JoinHandle` of the background task doing
+    /// the watching, so the caller can abort it when tearing this source
+    /// down (e.g. when `connect_remote` swaps in a different source) — the
+    /// watch does not stop on its own just because the source is dropped.
+    async fn watch(&self, sink: UnboundedSender<SessionEvent>) -> JoinHandle<()>;
+
+    /// Forward a message to the worker behind `session_id`. Dispatches the
+    /// underlying `orch` invocation through the command registry and returns
+    /// its command id immediately, rather than awaiting completion.
+    async fn forward_message(&self, session_id: &str, message: &str) -> Result<String, String>;
+
+    /// Cancel the session identified by `session_id`. Returns the dispatched
+    /// command's id immediately, rather than awaiting completion.
+    async fn cancel_session(&self, session_id: &str) -> Result<String, String>;
+}
@@ -0,0 +1,272 @@
+use super::SessionSource;
+use crate::capability::Action;
+use crate::process::CommandRegistry;
+use crate::session::{SessionEvent, SessionState};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::{async_runtime::JoinHandle, AppHandle};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Connection options accompanying the host in a `connect_remote(host, opts)`
+/// call from the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteConnectOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity_file: Option<String>,
+}
+
+/// Where to find the remote host and how to reach the `orch` CLI on it.
+/// Mirrors distant's connect-then-dispatch model, but transports over a
+/// plain `ssh` invocation rather than a custom protocol, so no extra daemon
+/// has to run on the remote end.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteOptions {
+    pub host: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity_file: Option<String>,
+}
+
+impl RemoteOptions {
+    pub fn new(host: String, opts: RemoteConnectOptions) -> Self {
+        Self {
+            host,
+            user: opts.user,
+            port: opts.port,
+            identity_file: opts.identity_file,
+        }
+    }
+}
+
+impl RemoteOptions {
+    /// `ssh` destination argument, e.g. `user@host`.
+    fn destination(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{}@{}", user, self.host),
+            None => self.host.clone(),
+        }
+    }
+
+    /// Build an `ssh` command that runs `orch_args` on the remote host.
+    /// `kill_on_drop` so an aborted watch task takes its `ssh` child down
+    /// with it instead of leaking an orphaned process.
+    fn ssh_command(&self, orch_args: &[&str]) -> Command {
+        let mut command = Command::new("ssh");
+        command.kill_on_drop(true);
+
+        if let Some(port) = self.port {
+            command.arg("-p").arg(port.to_string());
+        }
+        if let Some(identity_file) = &self.identity_file {
+            command.arg("-i").arg(identity_file);
+        }
+
+        command.arg(self.destination());
+        for part in remote_command_parts(orch_args) {
+            command.arg(part);
+        }
+        command
+    }
+
+    /// Full `ssh` argv (minus the `ssh` program name itself) for running
+    /// `orch_args` on the remote host, for use with `CommandRegistry::spawn`.
+    fn ssh_args(&self, orch_args: &[&str]) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if let Some(port) = self.port {
+            args.push("-p".to_string());
+            args.push(port.to_string());
+        }
+        if let Some(identity_file) = &self.identity_file {
+            args.push("-i".to_string());
+            args.push(identity_file.clone());
+        }
+
+        args.push(self.destination());
+        args.extend(remote_command_parts(orch_args));
+        args
+    }
+}
+
+/// `orch` plus `orch_args`, each shell-quoted. `ssh` joins every argument
+/// that follows the destination with a single space and hands the result
+/// to the remote user's shell for `sh -c` execution rather than exec'ing
+/// them as an argv array — so a `session_id`/message containing `; `,
+/// backticks, or `$(...)` would otherwise run as shell code on the remote
+/// host. Quoting each part keeps it a literal argument on the far end.
+fn remote_command_parts(orch_args: &[&str]) -> Vec<String> {
+    std::iter::once("orch").chain(orch_args.iter().copied()).map(shell_quote).collect()
+}
+
+/// Wrap `s` in single quotes, escaping embedded single quotes the POSIX
+/// shell way (close the quote, emit an escaped quote, reopen it), so `s`
+/// survives a remote `sh -c` as exactly one literal argument.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Sessions running on a build server reachable over SSH, so an operator can
+/// watch and control `orch` sessions without being logged into that host.
+pub struct RemoteSessionSource {
+    options: RemoteOptions,
+    app_handle: AppHandle,
+    registry: Arc<CommandRegistry>,
+}
+
+impl RemoteSessionSource {
+    pub fn new(options: RemoteOptions, app_handle: AppHandle, registry: Arc<CommandRegistry>) -> Self {
+        Self {
+            options,
+            app_handle,
+            registry,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionSource for RemoteSessionSource {
+    async fn snapshot(&self) -> Vec<SessionState> {
+        let output = match self.options.ssh_command(&["sessions", "--json"]).output().await {
+            Ok(output) if output.status.success() => output,
+            Ok(output) => {
+                eprintln!("orch sessions --json failed on {}: {}", self.options.host, String::from_utf8_lossy(&output.stderr));
+                return Vec::new();
+            }
+            Err(e) => {
+                eprintln!("Failed to reach {}: {}", self.options.host, e);
+                return Vec::new();
+            }
+        };
+
+        serde_json::from_slice(&output.stdout).unwrap_or_default()
+    }
+
+    async fn watch(&self, sink: UnboundedSender<SessionEvent>) -> JoinHandle<()> {
+        // The remote `orch` CLI streams newline-delimited session events for
+        // the lifetime of this command, in place of a local `notify` watcher.
+        // The spawn and the read loop both live inside the returned task so
+        // aborting its `JoinHandle` drops (and, via `kill_on_drop`, kills)
+        // the `ssh` child rather than leaking it.
+        let mut command = self.options.ssh_command(&["sessions", "--watch", "--json"]);
+        command.stdout(std::process::Stdio::piped());
+        let host = self.options.host.clone();
+
+        tauri::async_runtime::spawn(async move {
+            let mut child = match command.spawn() {
+                Ok(child) => child,
+                Err(e) => {
+                    eprintln!("Failed to start remote session watch on {}: {}", host, e);
+                    return;
+                }
+            };
+
+            let Some(stdout) = child.stdout.take() else {
+                eprintln!("Remote session watch on {} has no stdout", host);
+                return;
+            };
+
+            // Keep the child alive for the lifetime of the watch.
+            let _child = child;
+
+            let mut lines = BufReader::new(stdout).lines();
+            loop {
+                match lines.next_line().await {
+                    Ok(Some(line)) => match serde_json::from_str::<SessionEvent>(&line) {
+                        Ok(event) => {
+                            let _ = sink.send(event);
+                        }
+                        Err(e) => eprintln!("Ignoring malformed session event from {}: {}", host, e),
+                    },
+                    Ok(None) => break,
+                    Err(e) => {
+                        eprintln!("Remote session watch on {} failed: {}", host, e);
+                        break;
+                    }
+                }
+            }
+        })
+    }
+
+    async fn forward_message(&self, session_id: &str, message: &str) -> Result<String, String> {
+        let args = self.options.ssh_args(&["forward", session_id, message]);
+        Ok(CommandRegistry::spawn(
+            self.registry.clone(),
+            self.app_handle.clone(),
+            session_id.to_string(),
+            Action::Forward,
+            "ssh",
+            args,
+        ))
+    }
+
+    async fn cancel_session(&self, session_id: &str) -> Result<String, String> {
+        let args = self.options.ssh_args(&["cancel", session_id]);
+        Ok(CommandRegistry::spawn(
+            self.registry.clone(),
+            self.app_handle.clone(),
+            session_id.to_string(),
+            Action::Cancel,
+            "ssh",
+            args,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Join `remote_command_parts`' output the same way `ssh` joins the
+    /// arguments after its destination: with a single space, handed to a
+    /// shell for `sh -c` execution. Each of `orch_args` should
+    /// come back out exactly as it went in, not interpreted as shell code.
+    fn round_trip_through_shell(orch_args: &[&str]) -> Vec<String> {
+        let joined = remote_command_parts(orch_args).join(" ");
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("for a in {}; do echo \"$a\"; done", joined))
+            .output()
+            .expect("failed to run sh");
+        assert!(output.status.success(), "shell exited non-zero: {:?}", output);
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .map(|l| l.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn shell_quote_escapes_embedded_single_quotes() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn remote_command_parts_survive_command_chaining() {
+        let echoed = round_trip_through_shell(&["forward", "abc-123", "hello; rm -rf ~"]);
+        assert_eq!(echoed, vec!["orch", "forward", "abc-123", "hello; rm -rf ~"]);
+    }
+
+    #[test]
+    fn remote_command_parts_survive_backticks_and_command_substitution() {
+        let echoed = round_trip_through_shell(&["forward", "abc-123", "`whoami` and $(whoami)"]);
+        assert_eq!(echoed, vec!["orch", "forward", "abc-123", "`whoami` and $(whoami)"]);
+    }
+
+    #[test]
+    fn remote_command_parts_survive_embedded_quotes() {
+        let echoed = round_trip_through_shell(&["forward", "abc-123", "it's a \"test\""]);
+        assert_eq!(echoed, vec!["orch", "forward", "abc-123", "it's a \"test\""]);
+    }
+}